@@ -3,19 +3,26 @@ use crate::{
     bindings::i_uniswap_v2_factory::IUniswapV2Factory, errors::FactoryResult, ProtocolType,
 };
 use ethers::prelude::{builders::ContractCall, *};
-use std::sync::Arc;
+use std::borrow::Borrow;
 
 /// Represents a UniswapV2 factory.
+///
+/// Generic over `B: Borrow<M>` rather than hard-requiring `Arc<M>`, so callers can pass
+/// `Arc<M>`, `&M`, or an owned `M` depending on how they want to share the middleware.
 #[derive(Clone, Debug)]
-pub struct Factory<M> {
+pub struct Factory<B, M> {
     /// The factory contract.
-    contract: IUniswapV2Factory<M>,
+    contract: IUniswapV2Factory<B, M>,
 
     /// The factory protocol.
     protocol: ProtocolType,
+
+    /// Whether mutating calls built by this factory (e.g. `create_pair`) are converted to
+    /// legacy transactions. Only has an effect with the `legacy` feature enabled.
+    legacy: bool,
 }
 
-impl<M> Factory<M> {
+impl<B, M> Factory<B, M> {
     /// Returns the contract address of the factory.
     pub fn address(&self) -> Address {
         self.contract.address()
@@ -30,43 +37,69 @@ impl<M> Factory<M> {
     pub const fn pair_code_hash(&self) -> H256 {
         self.protocol.pair_code_hash()
     }
+
+    /// Returns whether mutating calls built by this factory are converted to legacy
+    /// transactions rather than EIP-1559 ones.
+    pub fn is_legacy(&self) -> bool {
+        self.legacy
+    }
+
+    /// Makes this factory convert every mutating call it builds (e.g. `create_pair`) into a
+    /// legacy transaction, for chains that don't support EIP-1559 typed envelopes.
+    ///
+    /// Requires the `legacy` feature. Calls from a factory without this setting can still be
+    /// converted one by one via
+    /// [`ContractCall::legacy`](ethers::contract::builders::ContractCall::legacy); calls from a
+    /// factory with it are returned already converted.
+    #[cfg(feature = "legacy")]
+    pub fn legacy(mut self) -> Self {
+        self.legacy = true;
+        self
+    }
 }
 
-impl<M: Middleware> Factory<M> {
+impl<B: Clone + Borrow<M>, M: Middleware> Factory<B, M> {
     /// Creates a new instance using the provided address.
-    pub fn new(client: Arc<M>, address: Address, protocol: ProtocolType) -> Self {
+    pub fn new(client: B, address: Address, protocol: ProtocolType) -> Self {
         // assert!(protocol.is_v2(), "protocol must be v2");
         let contract = IUniswapV2Factory::new(address, client);
-        Self { contract, protocol }
+        Self { contract, protocol, legacy: false }
     }
 
     /// Creates a new instance using the provided chain.
-    pub fn new_with_chain(client: Arc<M>, chain: Chain, protocol: ProtocolType) -> Option<Self> {
+    pub fn new_with_chain(client: B, chain: Chain, protocol: ProtocolType) -> Option<Self> {
         // assert!(protocol.is_v2(), "protocol must be v2");
         protocol.try_addresses(chain).0.map(|address| {
             let contract = IUniswapV2Factory::new(address, client);
-            Self { contract, protocol }
+            Self { contract, protocol, legacy: false }
         })
     }
 
     /// Returns a reference to the factory contract.
-    pub fn contract(&self) -> &IUniswapV2Factory<M> {
+    pub fn contract(&self) -> &IUniswapV2Factory<B, M> {
         &self.contract
     }
 
-    /// Returns a reference to the client.
-    pub fn client(&self) -> Arc<M> {
-        // self.contract.client()
-        todo!()
+    /// Returns the client backing this factory's contract.
+    pub fn client(&self) -> B {
+        self.contract.client()
     }
 
     /// Returns the contract call for creating a pair.
-    pub fn create_pair(&self, token_a: Address, token_b: Address) -> ContractCall<M, Address> {
+    ///
+    /// If this factory was built with [`Factory::legacy`], the call is pre-converted to a
+    /// legacy transaction.
+    pub fn create_pair(&self, token_a: Address, token_b: Address) -> ContractCall<B, M, Address> {
+        #[cfg(feature = "legacy")]
+        if self.legacy {
+            return self.contract.create_pair(token_a, token_b).legacy();
+        }
+
         self.contract.create_pair(token_a, token_b)
     }
 
     /// Returns the pair for two token addresses.
-    pub fn pair_for(&self, token_a: Address, token_b: Address) -> FactoryResult<Pair<M>, M> {
+    pub fn pair_for(&self, token_a: Address, token_b: Address) -> FactoryResult<Pair<B, M>, M> {
         let address = Library::pair_for(self, token_a, token_b)?;
         Ok(Pair::new(self.client(), address, self.protocol))
     }
@@ -1,16 +1,46 @@
 use super::{Factory, Library};
-use crate::{bindings::i_uniswap_v2_pair::IUniswapV2Pair, errors::PairResult, ProtocolType};
-use ethers::{abi::Token, contract::builders::ContractCall, core::abi::Detokenize, prelude::*};
-use std::{fmt, sync::Arc};
+use crate::{
+    bindings::i_uniswap_v2_pair::{IUniswapV2Pair, SyncFilter},
+    errors::{PairError, PairResult},
+    ProtocolType,
+};
+use ethers::{
+    abi::{self, ParamType, Token},
+    contract::builders::ContractCall,
+    core::abi::Detokenize,
+    prelude::*,
+};
+use futures::{Stream, StreamExt};
+use std::{borrow::Borrow, fmt, marker::PhantomData, sync::Arc};
+
+/// The 4-byte selector of Solidity's built-in `Error(string)`.
+const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+
+/// The 4-byte selector of Solidity's built-in `Panic(uint256)`.
+const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+/// The decoded reason behind a reverted multicall sub-call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RevertReason {
+    /// A `require`/`revert("...")`, i.e. Solidity's built-in `Error(string)`.
+    Reason(String),
+    /// A Solidity panic, e.g. `0x11` (arithmetic overflow) or `0x32` (out-of-bounds access).
+    Panic(U256),
+    /// An unrecognized 4-byte selector, preserved verbatim so callers can match it against their
+    /// own ABI.
+    Custom { selector: [u8; 4], data: Bytes },
+}
 
 type Tokens = (Address, Address);
 type Reserves = (u128, u128, u32);
 
 /// Represents a UniswapV2 liquidity pair, composed of 2 different ERC20 tokens.
-#[derive(Clone)]
-pub struct Pair<M> {
+///
+/// Generic over `B: Borrow<M>` rather than hard-requiring `Arc<M>`, so callers can pass
+/// `Arc<M>`, `&M`, or an owned `M` depending on how they want to share the middleware.
+pub struct Pair<B, M> {
     /// The client.
-    client: Arc<M>,
+    client: B,
 
     /// The pair address. Might not be currently deployed.
     address: Address,
@@ -26,10 +56,26 @@ pub struct Pair<M> {
 
     /// The protocol of the pair.
     protocol: ProtocolType,
+
+    _middleware: PhantomData<M>,
+}
+
+impl<B: Clone, M> Clone for Pair<B, M> {
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client.clone(),
+            address: self.address,
+            tokens: self.tokens,
+            deployed: self.deployed,
+            reserves: self.reserves,
+            protocol: self.protocol,
+            _middleware: PhantomData,
+        }
+    }
 }
 
 // Skip client in formatting
-impl<M> fmt::Debug for Pair<M> {
+impl<B, M> fmt::Debug for Pair<B, M> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Pair")
             .field("address", &self.address)
@@ -40,7 +86,7 @@ impl<M> fmt::Debug for Pair<M> {
     }
 }
 
-impl<M> fmt::Display for Pair<M> {
+impl<B, M> fmt::Display for Pair<B, M> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if self.tokens.is_some() {
             writeln!(f, "Pair:     {:?}", self.address)?;
@@ -62,21 +108,29 @@ impl<M> fmt::Display for Pair<M> {
     }
 }
 
-impl<M: Middleware> Pair<M> {
+impl<B: Clone + Borrow<M>, M: Middleware> Pair<B, M> {
     /// Creates a new instance using the provided client and address.
-    pub fn new(client: Arc<M>, address: Address, protocol: ProtocolType) -> Self {
-        Self { client, address, tokens: None, deployed: false, reserves: None, protocol }
+    pub fn new(client: B, address: Address, protocol: ProtocolType) -> Self {
+        Self {
+            client,
+            address,
+            tokens: None,
+            deployed: false,
+            reserves: None,
+            protocol,
+            _middleware: PhantomData,
+        }
     }
 
     /// Creates a new instance using the provided client, factory and tokens' addresses.
     pub fn new_with_factory(
-        client: Arc<M>,
-        factory: Factory,
+        client: B,
+        factory: Factory<B, M>,
         token0: Address,
         token1: Address,
     ) -> PairResult<Self, M> {
         let (token0, token1) = Library::sort_tokens(token0, token1)?;
-        let address = Library::pair_for(factory, token0, token1)?;
+        let address = Library::pair_for(&factory, token0, token1)?;
 
         Ok(Self {
             client,
@@ -85,11 +139,12 @@ impl<M: Middleware> Pair<M> {
             deployed: false,
             reserves: None,
             protocol: factory.protocol(),
+            _middleware: PhantomData,
         })
     }
 
     /// Returns the pair contract.
-    pub fn contract(&self) -> IUniswapV2Pair<M> {
+    pub fn contract(&self) -> IUniswapV2Pair<B, M> {
         IUniswapV2Pair::new(self.address, self.client.clone())
     }
 
@@ -126,30 +181,120 @@ impl<M: Middleware> Pair<M> {
     }
 
     /// Returns the contract calls for getting the addresses of the pair's tokens.
-    pub fn get_tokens(&self) -> (ContractCall<M, Address>, ContractCall<M, Address>) {
+    pub fn get_tokens(&self) -> (ContractCall<B, M, Address>, ContractCall<B, M, Address>) {
         let pair = self.contract();
         (pair.token_0(), pair.token_1())
     }
 
     /// Returns the contract call for getting the reserves of the pair.
-    pub fn get_reserves(&self) -> ContractCall<M, Reserves> {
+    pub fn get_reserves(&self) -> ContractCall<B, M, Reserves> {
         self.contract().get_reserves()
     }
 
-    /// Syncs the tokens and reserves of the pair by querying the blockchain.
+    /// Subscribes to this pair's `Sync(uint112 reserve0, uint112 reserve1)` event, yielding a
+    /// decoded [`Reserves`] update each time the pair's reserves change. The timestamp is the
+    /// target block's own `timestamp` (fetched per event), not the prior `blockTimestampLast` —
+    /// each update the stream yields is also written back into `self.reserves` (flipping
+    /// `deployed` to `true` on the first event), so after driving the stream callers can read the
+    /// latest values via [`reserves`](Pair::reserves). Nothing is written back while the stream
+    /// is not being polled.
+    ///
+    /// Built on [`Event::watch_with_meta`](ethers::contract::builders::Event::watch_with_meta),
+    /// which polls via log filters rather than a push-based subscription, so it works uniformly
+    /// across any [`Middleware`] instead of requiring one that supports `eth_subscribe`.
+    pub async fn watch_reserves(&mut self) -> PairResult<impl Stream<Item = Reserves> + '_, M> {
+        let watcher = self.contract().event::<SyncFilter>().watch_with_meta().await?;
+        let client = self.client.clone();
+
+        Ok(watcher
+            .then(move |(event, meta)| {
+                let client = client.clone();
+                async move {
+                    let timestamp = client
+                        .borrow()
+                        .get_block(meta.block_number)
+                        .await
+                        .ok()
+                        .flatten()
+                        .and_then(|block| u32::try_from(block.timestamp).ok())
+                        .unwrap_or(0);
+
+                    (event.reserve0, event.reserve1, timestamp)
+                }
+            })
+            .map(move |reserves| {
+                self.deployed = true;
+                self.reserves = Some(reserves);
+
+                reserves
+            }))
+    }
+
+    /// Syncs the tokens and reserves of the pair by querying the blockchain at the latest block.
+    ///
+    /// A sub-call failing with empty returndata is assumed to mean the pair has not been
+    /// deployed yet. A sub-call that reverts with a non-empty reason (`Error(string)`,
+    /// `Panic(uint256)`, or a custom error) surfaces as a [`PairError::Revert`] instead.
     ///
-    /// Assumes that any call failure means the pair has not been deployed yet.
+    /// Unlike this impl's other methods, `B = &M` can't be used here: the underlying
+    /// [`Multicall`] needs to own its client, so this (and [`sync_at`](Pair::sync_at)) are only
+    /// available for `B = Arc<M>` or an owned `M`.
     pub async fn sync(
         &mut self,
         sync_tokens: bool,
         sync_reserves: bool,
-    ) -> PairResult<&mut Self, M> {
+    ) -> PairResult<&mut Self, M>
+    where
+        B: Into<Arc<M>>,
+    {
+        self.sync_at(sync_tokens, sync_reserves, None).await
+    }
+
+    /// Same as [`sync`](Pair::sync), but pins every read to `block` instead of the latest block.
+    ///
+    /// All of the aggregate's sub-calls resolve against the same block, so tokens and reserves
+    /// can't straddle a block boundary — useful for reorg-safe snapshots and historical
+    /// reserve-at-block queries.
+    ///
+    /// Requires `B: Into<Arc<M>>` on top of the impl's `Borrow<M>` bound, since the underlying
+    /// [`Multicall`] needs its own owned `Arc<M>` — see the note on [`sync`](Pair::sync) about
+    /// `B = &M`. For the common `B = Arc<M>` case this is a cheap refcount bump rather than a
+    /// full clone of `M`.
+    pub async fn sync_at(
+        &mut self,
+        sync_tokens: bool,
+        sync_reserves: bool,
+        block: Option<BlockId>,
+    ) -> PairResult<&mut Self, M>
+    where
+        B: Into<Arc<M>>,
+    {
         // let sync_tokens = sync_tokens || self.tokens.is_none() || !self.deployed;
         // let sync_reserves = sync_reserves || self.reserves.is_none();
 
-        let multicall = Multicall::new(self.client.clone(), None).await?;
+        let multicall = Multicall::new(self.client.clone().into(), None).await?;
         let mut multicall = multicall.version(MulticallVersion::Multicall3);
+        if let Some(block) = block {
+            multicall = multicall.block(block);
+        }
+
+        self.add_sync_calls(&mut multicall, sync_tokens, sync_reserves);
+
+        let result = multicall.call_raw().await?;
+        apply_sync_result(self, sync_tokens, sync_reserves, result)?;
+
+        Ok(self)
+    }
 
+    /// Adds this pair's `get_tokens()`/`get_reserves()` calls to a multicall, depending on which
+    /// of `sync_tokens`/`sync_reserves` are requested. Used by [`sync`](Pair::sync) and
+    /// [`sync_pairs`] to build up a shared aggregate call.
+    fn add_sync_calls(
+        &self,
+        multicall: &mut Multicall<M>,
+        sync_tokens: bool,
+        sync_reserves: bool,
+    ) {
         if sync_tokens {
             let calls = self.get_tokens();
             multicall.add_call(calls.0, true);
@@ -159,86 +304,212 @@ impl<M: Middleware> Pair<M> {
         if sync_reserves {
             multicall.add_call(self.get_reserves(), true);
         }
+    }
+}
 
-        let result = multicall.call_raw().await?;
+/// Syncs many pairs' tokens and/or reserves in a single [`MulticallVersion::Multicall3`]
+/// round-trip, instead of one multicall per pair.
+///
+/// Every pair's `get_tokens()`/`get_reserves()` calls (all with `allow_failure = true`) are
+/// appended into one aggregate call, which is then sliced back into per-pair chunks. A failed
+/// sub-call for a given pair only marks that pair as undeployed; it does not affect the others.
+pub async fn sync_pairs<B: Clone + Borrow<M> + Into<Arc<M>>, M: Middleware>(
+    client: B,
+    pairs: &mut [Pair<B, M>],
+    sync_tokens: bool,
+    sync_reserves: bool,
+) -> PairResult<(), M> {
+    sync_pairs_at(client, pairs, sync_tokens, sync_reserves, None).await
+}
 
-        // Assume any call failure means the contract has not been deployed yet
-        match (sync_tokens, sync_reserves) {
-            (true, true) => {
-                let tokens = parse_tokens_result(result[0..2].to_vec())?;
-                let reserves = parse_reserves_result(result[2..].to_vec())?;
+/// Same as [`sync_pairs`], but pins every read in the aggregate to `block` instead of the latest
+/// block, so all pairs resolve against one consistent snapshot.
+pub async fn sync_pairs_at<B: Clone + Borrow<M> + Into<Arc<M>>, M: Middleware>(
+    client: B,
+    pairs: &mut [Pair<B, M>],
+    sync_tokens: bool,
+    sync_reserves: bool,
+    block: Option<BlockId>,
+) -> PairResult<(), M> {
+    if pairs.is_empty() || !(sync_tokens || sync_reserves) {
+        return Ok(())
+    }
 
-                if tokens.is_none() || reserves.is_none() {
-                    self.tokens = None;
-                    self.deployed = false;
-                    return Ok(self)
-                }
+    let multicall = Multicall::new(client.clone().into(), None).await?;
+    let mut multicall = multicall.version(MulticallVersion::Multicall3);
+    if let Some(block) = block {
+        multicall = multicall.block(block);
+    }
 
-                self.deployed = true;
-                self.tokens = tokens;
-                self.reserves = reserves;
-            }
-            (true, false) => {
-                let tokens = parse_tokens_result(result)?;
+    for pair in pairs.iter() {
+        pair.add_sync_calls(&mut multicall, sync_tokens, sync_reserves);
+    }
 
-                if tokens.is_none() {
-                    self.tokens = None;
-                    self.deployed = false;
-                    return Ok(self)
-                }
+    let result = multicall.call_raw().await?;
 
-                self.deployed = true;
-                self.tokens = tokens;
+    let stride = usize::from(sync_tokens) * 2 + usize::from(sync_reserves);
+    for (pair, chunk) in pairs.iter_mut().zip(result.chunks(stride)) {
+        match apply_sync_result(pair, sync_tokens, sync_reserves, chunk.to_vec()) {
+            // A revert is specific to this pair (e.g. an address with code that isn't actually
+            // a pair); mark just this pair undeployed and keep going instead of aborting the
+            // whole batch.
+            Err(PairError::Revert(_)) => pair.deployed = false,
+            other => other?,
+        }
+    }
+
+    Ok(())
+}
+
+/// Applies a (possibly partial) multicall result produced by [`Pair::add_sync_calls`] back onto
+/// a pair. A sub-call failing with empty returndata marks the pair as not deployed; a genuine
+/// revert instead propagates as `Err(PairError::Revert(..))`.
+fn apply_sync_result<B, M: Middleware>(
+    pair: &mut Pair<B, M>,
+    sync_tokens: bool,
+    sync_reserves: bool,
+    result: Vec<Token>,
+) -> PairResult<(), M> {
+    match (sync_tokens, sync_reserves) {
+        (true, true) => {
+            let tokens = parse_tokens_result(result[0..2].to_vec())?;
+            let reserves = parse_reserves_result(result[2..].to_vec())?;
+
+            if tokens.is_none() || reserves.is_none() {
+                pair.tokens = None;
+                pair.deployed = false;
+                return Ok(())
             }
-            (false, true) => {
-                let reserves = parse_reserves_result(result)?;
 
-                if reserves.is_none() {
-                    self.tokens = None;
-                    self.deployed = false;
-                    return Ok(self)
-                }
+            pair.deployed = true;
+            pair.tokens = tokens;
+            pair.reserves = reserves;
+        }
+        (true, false) => {
+            let tokens = parse_tokens_result(result)?;
 
-                self.deployed = true;
-                self.reserves = reserves;
+            if tokens.is_none() {
+                pair.tokens = None;
+                pair.deployed = false;
+                return Ok(())
             }
-            (false, false) => {}
+
+            pair.deployed = true;
+            pair.tokens = tokens;
         }
+        (false, true) => {
+            let reserves = parse_reserves_result(result)?;
 
-        Ok(self)
+            if reserves.is_none() {
+                pair.tokens = None;
+                pair.deployed = false;
+                return Ok(())
+            }
+
+            pair.deployed = true;
+            pair.reserves = reserves;
+        }
+        (false, false) => {}
     }
+
+    Ok(())
 }
 
-/// Parses (bool, String) from a vector of tokens.
-fn parse_errors(tokens: Vec<Token>) -> Vec<Option<String>> {
-    type ErrorResult = (bool, String);
-    let mut msgs = vec![];
+/// The status of one allow-failure sub-call, read off its `(bool success, ..)` wrapper.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum CallStatus {
+    /// The sub-call succeeded. Its decoded value isn't captured here; callers that need it
+    /// decode the whole token group directly via [`Detokenize`].
+    Success,
+    /// The sub-call reverted, carrying its raw returndata (possibly empty).
+    Reverted(Bytes),
+}
 
-    for token in tokens {
-        match ErrorResult::from_tokens(vec![token]) {
-            Ok((_, msg)) => msgs.push(Some(msg)),
-            Err(_) => msgs.push(None),
+/// Reads the `(bool success, ..)` wrapper off a single allow-failure multicall token, without
+/// assuming anything about the shape of a *successful* sub-call's decoded value. Returns `None`
+/// if `token` isn't even shaped like an allow-failure result.
+fn call_status(token: Token) -> Option<CallStatus> {
+    let Token::Tuple(mut fields) = token else { return None };
+    if fields.len() != 2 {
+        return None
+    }
+    let value = fields.pop()?;
+    let Token::Bool(success) = fields.pop()? else { return None };
+
+    if success {
+        Some(CallStatus::Success)
+    } else {
+        match value {
+            Token::Bytes(data) => Some(CallStatus::Reverted(Bytes::from(data))),
+            _ => None,
         }
     }
+}
 
-    msgs
+/// Reads the `(bool success, ..)` wrapper off every element of an allow-failure multicall result.
+fn parse_call_data(tokens: Vec<Token>) -> Vec<Option<CallStatus>> {
+    tokens.into_iter().map(call_status).collect()
 }
 
-/// Parses a multicall result from a vector of tokens, returning None if the call returned an
-/// error.
+/// Decodes a reverted sub-call's raw returndata into a [`RevertReason`].
+///
+/// Recognizes the built-in `Error(string)` and `Panic(uint256)` selectors; any other non-empty
+/// 4-byte selector is preserved verbatim as [`RevertReason::Custom`].
+fn decode_revert_reason(data: &[u8]) -> RevertReason {
+    let mut selector = [0u8; 4];
+    let prefix_len = data.len().min(4);
+    selector[..prefix_len].copy_from_slice(&data[..prefix_len]);
+    let args = data.get(4..).unwrap_or_default();
+
+    let custom = || RevertReason::Custom { selector, data: Bytes::from(data.to_vec()) };
+
+    match selector {
+        ERROR_SELECTOR => abi::decode(&[ParamType::String], args)
+            .ok()
+            .and_then(|mut tokens| tokens.pop())
+            .and_then(Token::into_string)
+            .map_or_else(custom, RevertReason::Reason),
+        PANIC_SELECTOR => abi::decode(&[ParamType::Uint(256)], args)
+            .ok()
+            .and_then(|mut tokens| tokens.pop())
+            .and_then(Token::into_uint)
+            .map_or_else(custom, RevertReason::Panic),
+        _ => custom(),
+    }
+}
+
+/// Parses a multicall result from a vector of tokens, returning `None` if at least one
+/// allowed-to-fail sub-call came back with empty returndata and none reverted with a reason (i.e.
+/// the contract isn't deployed). A sub-call that reverted with a non-empty reason instead produces
+/// an `Err(PairError::Revert(..))`.
 fn parse_result<M: Middleware, D: Detokenize>(tokens: Vec<Token>) -> PairResult<Option<D>, M> {
     let res = D::from_tokens(tokens.clone());
     match res {
         Err(e) => {
-            // Failed to decode
-            let errors = parse_errors(tokens);
-            if errors.iter().any(|s| s.is_none()) {
-                // Failed to decode errors too
-                Err(ContractError::DetokenizationError(e).into())
-            } else {
-                // All calls failed while allowed
-                Ok(None)
+            // Failed to decode the success shape as a whole; inspect each sub-call's
+            // `(bool, ..)` wrapper individually instead, since a single reverted sub-call in an
+            // otherwise-successful group is enough to make the group-wide decode fail.
+            let mut reverted_empty = false;
+            for status in parse_call_data(tokens) {
+                match status {
+                    Some(CallStatus::Reverted(data)) if !data.is_empty() => {
+                        return Err(PairError::Revert(decode_revert_reason(&data)))
+                    }
+                    Some(CallStatus::Reverted(_)) => reverted_empty = true,
+                    Some(CallStatus::Success) => {}
+                    // Not even shaped like an allow-failure result.
+                    None => return Err(ContractError::DetokenizationError(e).into()),
+                }
             }
+
+            // Every sub-call succeeded, so the group just didn't decode into `D`.
+            if !reverted_empty {
+                return Err(ContractError::DetokenizationError(e).into())
+            }
+
+            // The failed sub-calls all came back with empty returndata: the contract isn't
+            // deployed.
+            Ok(None)
         }
         Ok(res) => Ok(Some(res)),
     }
@@ -285,13 +556,14 @@ mod tests {
     use super::*;
     use crate::{contracts::address, ProtocolType};
 
-    fn default_pair() -> Pair<Provider<Http>> {
+    fn default_pair() -> Pair<Arc<Provider<Http>>, Provider<Http>> {
         let chain = Chain::Mainnet;
         let weth = address("WETH", chain);
         let usdc = address("USDC", chain);
         let provider = MAINNET.provider();
         let client = Arc::new(provider);
-        let factory = Factory::new_with_chain(chain, ProtocolType::UniswapV2).unwrap();
+        let factory =
+            Factory::new_with_chain(client.clone(), chain, ProtocolType::UniswapV2).unwrap();
 
         Pair::new_with_factory(client, factory, weth, usdc).unwrap()
     }
@@ -299,14 +571,12 @@ mod tests {
     #[test]
     fn test_parsing() {
         let addresses = (Address::random(), Address::random());
-        // let tokens = vec![Token::Address(addresses.0), Token::Address(addresses.1)];
         let reserve_uints = (69u128, 420u128, 1337u32);
         let reserves = vec![
             Token::Uint(reserve_uints.0.into()),
             Token::Uint(reserve_uints.1.into()),
             Token::Uint(reserve_uints.2.into()),
         ];
-        let error_message = "Error message".to_string();
 
         type SuccessResult = ((bool, Address), (bool, Address), (bool, Reserves));
         let success_result: SuccessResult =
@@ -316,54 +586,86 @@ mod tests {
             Token::Tuple(vec![Token::Bool(true), Token::Address(addresses.1)]),
             Token::Tuple(vec![Token::Bool(true), Token::Tuple(reserves)]),
         ];
-        type FailureResult = ((bool, String), (bool, String), (bool, String));
-        let failure_result: FailureResult = (
-            (false, error_message.clone()),
-            (false, error_message.clone()),
-            (false, error_message.clone()),
-        );
-        let failure_tokens = vec![
-            Token::Tuple(vec![Token::Bool(false), Token::String(error_message.clone())]),
-            Token::Tuple(vec![Token::Bool(false), Token::String(error_message.clone())]),
-            Token::Tuple(vec![Token::Bool(false), Token::String(error_message.clone())]),
+
+        // Allow-failure sub-calls that reverted with empty returndata, e.g. a pair contract
+        // that simply doesn't exist yet.
+        let not_deployed_tokens = vec![
+            Token::Tuple(vec![Token::Bool(false), Token::Bytes(vec![])]),
+            Token::Tuple(vec![Token::Bool(false), Token::Bytes(vec![])]),
+        ];
+
+        let error_message = "Error message".to_string();
+        let mut error_data = ERROR_SELECTOR.to_vec();
+        error_data.extend(abi::encode(&[Token::String(error_message.clone())]));
+
+        let panic_code = U256::from(0x11);
+        let mut panic_data = PANIC_SELECTOR.to_vec();
+        panic_data.extend(abi::encode(&[Token::Uint(panic_code)]));
+
+        let custom_selector = [0xde, 0xad, 0xbe, 0xef];
+        let mut custom_data = custom_selector.to_vec();
+        custom_data.extend([1, 2, 3, 4]);
+
+        // A genuine revert on the `token_0()` sub-call.
+        let revert_tokens = vec![
+            Token::Tuple(vec![Token::Bool(false), Token::Bytes(error_data.clone())]),
+            Token::Tuple(vec![Token::Bool(true), Token::Address(addresses.1)]),
         ];
 
-        // parse_errors
+        // parse_call_data
 
-        let errors = parse_errors(success_tokens.clone());
-        assert_eq!(errors.len(), 3);
-        for e in errors {
-            assert!(e.is_none());
+        let statuses = parse_call_data(success_tokens.clone());
+        assert_eq!(statuses.len(), 3);
+        for status in statuses {
+            assert_eq!(status.unwrap(), CallStatus::Success);
         }
 
-        let errors = parse_errors(failure_tokens.clone());
-        assert_eq!(errors.len(), 3);
-        for e in errors {
-            assert_eq!(e.unwrap(), error_message.clone());
+        let statuses = parse_call_data(not_deployed_tokens.clone());
+        assert_eq!(statuses.len(), 2);
+        for status in statuses {
+            assert_eq!(status.unwrap(), CallStatus::Reverted(Bytes::from(Vec::<u8>::new())));
         }
 
+        let statuses = parse_call_data(revert_tokens.clone());
+        assert_eq!(statuses, vec![
+            Some(CallStatus::Reverted(Bytes::from(error_data.clone()))),
+            Some(CallStatus::Success),
+        ]);
+
+        // decode_revert_reason
+
+        assert_eq!(decode_revert_reason(&error_data), RevertReason::Reason(error_message.clone()));
+        assert_eq!(decode_revert_reason(&panic_data), RevertReason::Panic(panic_code));
+        assert_eq!(
+            decode_revert_reason(&custom_data),
+            RevertReason::Custom { selector: custom_selector, data: Bytes::from(custom_data.clone()) }
+        );
+
         // parse_result
 
         let result = parse_result::<Provider<Http>, SuccessResult>(success_tokens.clone()).unwrap();
         assert_eq!(result.unwrap(), success_result);
 
-        let result = parse_result::<Provider<Http>, FailureResult>(failure_tokens.clone()).unwrap();
-        assert_eq!(result.unwrap(), failure_result);
-
         // parse_tokens_result
 
         let result = parse_tokens_result::<Provider<Http>>(success_tokens[0..2].to_vec()).unwrap();
         assert_eq!(result.unwrap(), addresses);
 
-        let result = parse_tokens_result::<Provider<Http>>(failure_tokens.clone());
+        let result = parse_tokens_result::<Provider<Http>>(not_deployed_tokens.clone());
         assert!(result.unwrap().is_none());
 
+        let err = parse_tokens_result::<Provider<Http>>(revert_tokens).unwrap_err();
+        assert!(matches!(
+            err,
+            PairError::Revert(RevertReason::Reason(msg)) if msg == error_message
+        ));
+
         // parse_reserves_result
 
         let result = parse_reserves_result::<Provider<Http>>(success_tokens[2..].to_vec()).unwrap();
         assert_eq!(result.unwrap(), reserve_uints);
 
-        let result = parse_reserves_result::<Provider<Http>>(failure_tokens);
+        let result = parse_reserves_result::<Provider<Http>>(not_deployed_tokens);
         assert!(result.unwrap().is_none());
     }
 
@@ -387,4 +689,49 @@ mod tests {
         assert_ne!(reserves.1, 0);
         assert_ne!(reserves.2, 0);
     }
+
+    #[tokio::test]
+    async fn test_sync_at() {
+        let mut pair = default_pair();
+
+        let block = BlockId::Number(BlockNumber::Number(17_000_000.into()));
+        pair.sync_at(true, true, Some(block)).await.unwrap();
+
+        assert!(pair.deployed());
+        let reserves = pair.reserves().unwrap();
+        assert_ne!(reserves.0, 0);
+        assert_ne!(reserves.1, 0);
+    }
+
+    #[tokio::test]
+    async fn test_sync_pairs() {
+        let client = MAINNET.provider();
+        let client = Arc::new(client);
+        let mut pairs = vec![default_pair(), default_pair()];
+
+        sync_pairs(client, &mut pairs, true, true).await.unwrap();
+
+        for pair in &pairs {
+            assert!(pair.deployed());
+            let reserves = pair.reserves().unwrap();
+            assert_ne!(reserves.0, 0);
+            assert_ne!(reserves.1, 0);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_watch_reserves() {
+        let mut pair = default_pair();
+        pair.sync(false, true).await.unwrap();
+        let synced_reserves = pair.reserves().unwrap();
+
+        let mut stream = pair.watch_reserves().await.unwrap();
+
+        // Mainnet WETH/USDC reserves change far too often to reliably wait for an update in a
+        // test, so just make sure the stream can be established without erroring.
+        let _ = tokio::time::timeout(std::time::Duration::from_millis(1), stream.next()).await;
+        drop(stream);
+
+        assert_eq!(pair.reserves().unwrap(), synced_reserves);
+    }
 }